@@ -2,38 +2,68 @@
 ///
 /// See [main.rs] for an example of how to run this bot.
 use std::{
-    collections::VecDeque,
+    collections::{HashMap, VecDeque},
+    path::PathBuf,
     sync::Arc,
+    thread,
     time::{Duration, Instant},
 };
 
-use log::info;
+use log::{error, info};
 use tokio::runtime::Runtime;
+use vek::Vec3;
 use veloren_client::{addr::ConnectionArgs, Client, ClientType, Event as VelorenEvent};
 use veloren_common::{
     clock::Clock,
     comp::{
+        inventory::slot::InvSlotId,
         invite::InviteKind,
-        item::{ItemDesc, ItemI18n},
-        ChatType, ControllerInputs,
+        item::{ItemDefinitionId, ItemDesc, ItemI18n},
+        ChatType, ControllerInputs, Dir, Inventory,
     },
+    trade::{PendingTrade, TradeAction, TradePhase},
     uid::Uid,
     ViewDistances,
 };
 
+use crate::{
+    command::{self, CommandError, CommandName},
+    roles::{Role, RoleStore},
+};
+
 const CLIENT_TPS: Duration = Duration::from_millis(33);
 const BOT_EVENT_INTERVAL: Duration = Duration::from_secs(1);
 
+/// How close, in blocks, the bot needs to be to its anchor point before it stops walking and
+/// just faces the configured direction.
+const ANCHOR_ARRIVAL_RADIUS: f32 = 0.5;
+
+/// `ItemDefinitionId` of the coin item used to pay for trades.
+const COIN_ITEM_ID: &str = "common.items.utility.coins";
+
+/// Backoff before the first reconnect attempt after a dropped connection, doubling after each
+/// failed attempt up to `RECONNECT_MAX_BACKOFF`.
+const RECONNECT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_BACKOFF: Duration = Duration::from_mins(1);
+
 enum BotEvent {
     InvitePlayer(Uid),
     KickPlayer(Uid),
     SendTell(String, String),
+    AcceptTradeInvite(Uid),
+    PerformTradeAction(TradeAction),
 }
 
 /// An active connection to the Veloren server that will attempt to run every time the `tick`
 /// function is called.
 pub struct Bot {
-    admins: Vec<String>,
+    game_server: String,
+    auth_server: String,
+    username: String,
+    password: String,
+    character: String,
+
+    roles: RoleStore,
 
     client: Client,
     clock: Clock,
@@ -42,6 +72,21 @@ pub struct Bot {
     last_bot_event: Instant,
 
     item_i18n: ItemI18n,
+
+    buy_prices: HashMap<String, u32>,
+    sell_prices: HashMap<String, u32>,
+
+    anchor_pos: Vec3<f32>,
+    anchor_ori: f32,
+
+    announcements: Vec<String>,
+    announcement_interval: Duration,
+    last_announcement: Instant,
+    next_announcement: usize,
+
+    /// The offers seen on the last tick a trade was pending, so `manage_trade` only reacts when
+    /// something actually changed instead of re-sending the same action every tick.
+    last_trade_offer: Option<[HashMap<InvSlotId, u32>; 2]>,
 }
 
 impl Bot {
@@ -54,73 +99,70 @@ impl Bot {
         username: String,
         password: &str,
         character: &str,
-        admins: Vec<String>,
+        owners: Vec<String>,
+        roles_path: PathBuf,
+        buy_prices: HashMap<String, u32>,
+        sell_prices: HashMap<String, u32>,
+        position: [f32; 3],
+        orientation: f32,
+        announcements: Vec<String>,
+        announcement_interval: Duration,
     ) -> Result<Self, String> {
-        info!("Connecting to veloren");
-
-        let mut client = connect_to_veloren(game_server, auth_server, &username, password)?;
-        let mut clock = Clock::new(CLIENT_TPS);
-
-        client.load_character_list();
-
-        while client.character_list().loading {
-            client
-                .tick(ControllerInputs::default(), clock.dt())
-                .map_err(|error| format!("{error:?}"))?;
-            clock.tick();
-        }
-
-        let character_id = client
-            .character_list()
-            .characters
-            .iter()
-            .find(|character_item| character_item.character.alias == character)
-            .ok_or_else(|| format!("No character named {character}"))?
-            .character
-            .id
-            .ok_or("Failed to get character ID")?;
-
-        info!("Selecting a character");
-
-        // This loop waits and retries requesting the character in the case that the character has
-        // logged out too recently.
-        while client.position().is_none() {
-            client.request_character(
-                character_id,
-                ViewDistances {
-                    terrain: 4,
-                    entity: 4,
-                },
-            );
-
-            client
-                .tick(ControllerInputs::default(), clock.dt())
-                .map_err(|error| format!("{error:?}"))?;
-            clock.tick();
-        }
+        let (client, clock) = connect_and_select_character(
+            game_server.clone(),
+            auth_server,
+            &username,
+            password,
+            character,
+        )?;
 
         Ok(Bot {
-            admins,
+            game_server,
+            auth_server: auth_server.to_string(),
+            username,
+            password: password.to_string(),
+            character: character.to_string(),
+
+            roles: RoleStore::load(owners, roles_path)?,
             client,
             clock,
             events: VecDeque::new(),
             last_bot_event: Instant::now(),
             item_i18n: ItemI18n::new_expect(),
+            buy_prices,
+            sell_prices,
+            anchor_pos: Vec3::from(position),
+            anchor_ori: orientation,
+            announcements,
+            announcement_interval,
+            last_announcement: Instant::now(),
+            next_announcement: 0,
+            last_trade_offer: None,
         })
     }
 
     /// Run the bot for a single tick. This should be called in a loop. Returns `true` if the loop
     /// should continue running.
     pub fn tick(&mut self) -> Result<bool, String> {
-        let veloren_events = self
-            .client
-            .tick(ControllerInputs::default(), self.clock.dt())
-            .map_err(|error| format!("{error:?}"))?;
+        let veloren_events = match self.client.tick(self.anchor_inputs(), self.clock.dt()) {
+            Ok(veloren_events) => veloren_events,
+            Err(error) => {
+                error!("Lost connection to veloren, reconnecting: {error:?}");
+                self.reconnect();
+                return Ok(true);
+            }
+        };
 
         for event in veloren_events {
             self.handle_veloren_event(event)?;
         }
 
+        if let Some((inviter, _, InviteKind::Trade)) = self.client.invite() {
+            self.events.push_back(BotEvent::AcceptTradeInvite(inviter));
+        }
+
+        self.manage_trade()?;
+
         if self.last_bot_event.elapsed() >= BOT_EVENT_INTERVAL {
             if let Some(next_bot_event) = self.events.pop_front() {
                 self.handle_bot_event(next_bot_event)?;
@@ -129,8 +171,18 @@ impl Bot {
             self.last_bot_event = Instant::now();
         }
 
-        if !self.client.is_dead() {
-            self.client.send_command("kill".to_string(), Vec::new());
+        if !self.announcements.is_empty()
+            && self.last_announcement.elapsed() >= self.announcement_interval
+        {
+            let message = self.announcements[self.next_announcement].clone();
+
+            // Sent directly rather than through `events`, which only drains one entry per
+            // `BOT_EVENT_INTERVAL` and can be backlogged by tells/invites; that would otherwise
+            // delay announcements well past `announcement_interval`.
+            self.client
+                .send_command("region".to_string(), vec![message]);
+            self.next_announcement = (self.next_announcement + 1) % self.announcements.len();
+            self.last_announcement = Instant::now();
         }
 
         self.clock.tick();
@@ -138,6 +190,38 @@ impl Bot {
         Ok(true)
     }
 
+    /// Tears down the current connection and retries [`connect_and_select_character`] with
+    /// exponential backoff until it succeeds. All other bot state (roles, queued events, prices,
+    /// anchor, announcements) is left untouched, so the bot resumes exactly where it left off.
+    fn reconnect(&mut self) {
+        let mut backoff = RECONNECT_INITIAL_BACKOFF;
+
+        loop {
+            thread::sleep(backoff);
+
+            match connect_and_select_character(
+                self.game_server.clone(),
+                &self.auth_server,
+                &self.username,
+                &self.password,
+                &self.character,
+            ) {
+                Ok((client, clock)) => {
+                    self.client = client;
+                    self.clock = clock;
+
+                    info!("Reconnected to veloren");
+
+                    return;
+                }
+                Err(error) => {
+                    backoff = (backoff * 2).min(RECONNECT_MAX_BACKOFF);
+                    error!("Reconnect attempt failed, retrying in {backoff:?}: {error}");
+                }
+            }
+        }
+    }
+
     /// Consume and manage a client-side Veloren event. Returns a boolean indicating whether the
     /// bot should continue processing events.
     fn handle_veloren_event(&mut self, event: VelorenEvent) -> Result<(), String> {
@@ -156,112 +240,22 @@ impl Bot {
                     return Err("Failed to get sender UID".to_string());
                 };
                 let sender_name = self.find_player_alias(&sender_uid)?.clone();
-                let message_parts: Vec<&str> = message
-                    .content()
-                    .as_plain()
-                    .unwrap_or("")
-                    .split_whitespace()
-                    .collect();
-                let command = message_parts.first().ok_or("Failed to get command")?;
-                let args = &message_parts[1..];
-
-                match *command {
-                    "inv" => {
-                        if !self.admins.contains(&sender_name) {
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name.clone(),
-                                "You are not an admin".to_string(),
-                            ));
-
-                            return Ok(());
-                        }
-
-                        if args.is_empty() {
-                            self.events.push_back(BotEvent::InvitePlayer(sender_uid));
-                        } else {
-                            for arg in args {
-                                if let Some(uid) = self.find_uid(arg) {
-                                    self.events.push_back(BotEvent::InvitePlayer(*uid));
-                                    self.events.push_back(BotEvent::SendTell(
-                                        sender_name.clone(),
-                                        format!("Invited {}", arg),
-                                    ));
-                                } else {
-                                    self.events.push_back(BotEvent::SendTell(
-                                        sender_name.clone(),
-                                        format!("Failed to find player {}", arg),
-                                    ));
-                                }
-                            }
-                        }
-                    }
-                    "kick" => {
-                        if !self.admins.contains(&sender_name) {
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name,
-                                "You are not an admin".to_string(),
-                            ));
-
-                            return Ok(());
-                        }
-
-                        if args.is_empty() {
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name,
-                                "You must specify a player to kick".to_string(),
-                            ));
-
-                            return Ok(());
-                        }
-
-                        for arg in args {
-                            if let Some(uid) = self.find_uid(arg) {
-                                self.events.push_back(BotEvent::KickPlayer(*uid));
-                            } else {
-                                self.events.push_back(BotEvent::SendTell(
-                                    sender_name.clone(),
-                                    format!("Failed to find player {}", arg),
-                                ));
-                            }
-                        }
+                let content = message.content().as_plain().unwrap_or("");
+                let sender_role = self.roles.role_of(&sender_name);
+
+                match command::parse(content, sender_role) {
+                    Ok(parsed) => self.dispatch_command(parsed, sender_uid, sender_name)?,
+                    Err(CommandError::Unknown) => {}
+                    Err(CommandError::InsufficientRole { required }) => {
+                        self.events.push_back(BotEvent::SendTell(
+                            sender_name,
+                            format!("You must be at least {required:?} to do that"),
+                        ));
                     }
-                    "admin" => {
-                        if !self.admins.contains(&sender_name) {
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name,
-                                "You are not an admin".to_string(),
-                            ));
-
-                            return Ok(());
-                        }
-
-                        if args.is_empty() {
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name,
-                                "You must specify a player to promote".to_string(),
-                            ));
-
-                            return Ok(());
-                        }
-
-                        for arg in args {
-                            if !self.client.players().any(|player| player == *arg) {
-                                self.events.push_back(BotEvent::SendTell(
-                                    sender_name.clone(),
-                                    format!("Failed to find player {}", arg),
-                                ));
-
-                                continue;
-                            }
-
-                            self.admins.push(arg.to_string());
-                            self.events.push_back(BotEvent::SendTell(
-                                sender_name.clone(),
-                                format!("Promoted {}", arg),
-                            ));
-                        }
+                    Err(CommandError::ArgCount { message }) => {
+                        self.events
+                            .push_back(BotEvent::SendTell(sender_name, message));
                     }
-                    _ => {}
                 }
             }
             VelorenEvent::GroupInventoryUpdate(item, uid) => {
@@ -293,11 +287,362 @@ impl Bot {
                 self.client
                     .send_command("tell".to_string(), vec![name, message]);
             }
+            BotEvent::AcceptTradeInvite(_inviter) => {
+                self.client.accept_invite();
+            }
+            BotEvent::PerformTradeAction(action) => {
+                self.client.perform_trade_action(action);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Computes the movement and facing inputs that walk the bot back toward its configured
+    /// anchor point and, once there, turns it to face `anchor_ori`.
+    fn anchor_inputs(&self) -> ControllerInputs {
+        let Some(pos) = self.client.position() else {
+            return ControllerInputs::default();
+        };
+
+        let offset = self.anchor_pos - pos;
+
+        if offset.xy().magnitude() <= ANCHOR_ARRIVAL_RADIUS {
+            return ControllerInputs {
+                look_dir: Dir::from_unnormalized(Vec3::new(
+                    self.anchor_ori.cos(),
+                    self.anchor_ori.sin(),
+                    0.0,
+                ))
+                .unwrap_or_default(),
+                ..Default::default()
+            };
+        }
+
+        ControllerInputs {
+            move_dir: offset.xy().normalized(),
+            look_dir: Dir::from_unnormalized(offset).unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    /// Drives an in-progress trade to completion: prices whatever the other party has offered
+    /// using `buy_prices`, prices whatever we've offered using `sell_prices` (placed there by the
+    /// `buy` command), and balances our side of the trade with coins to match. Any coins either
+    /// party has already placed count toward that party's value. Accepts once both sides agree
+    /// on a non-vacuous value, declines if the other party's offer can't be priced.
+    ///
+    /// Only queues a reaction when the offer actually changed since the last tick, and the
+    /// reaction itself goes through the throttled `events` queue like every other outgoing
+    /// action, rather than calling `perform_trade_action` at tick rate.
+    fn manage_trade(&mut self) -> Result<(), String> {
+        let Some((_, trade, _)) = self.client.pending_trade() else {
+            self.last_trade_offer = None;
+            return Ok(());
+        };
+        let trade = trade.clone();
+
+        if self.last_trade_offer.as_ref() == Some(&trade.offers) {
+            return Ok(());
+        }
+        self.last_trade_offer = Some(trade.offers.clone());
+
+        let own_uid = self.client.uid().ok_or("Failed to get own UID")?;
+        let own_party = trade
+            .which_party(own_uid)
+            .ok_or("Bot is not a party to its own trade")?;
+        let their_party = 1 - own_party;
+
+        let their_items_value = match self.price_offer(&trade, their_party, &self.buy_prices) {
+            Some(value) => value,
+            None => {
+                self.events
+                    .push_back(BotEvent::PerformTradeAction(TradeAction::Decline));
+                return Ok(());
+            }
+        };
+        let their_value = their_items_value + self.coin_value(&trade, their_party);
+
+        let own_items_value = match self.price_offer(&trade, own_party, &self.sell_prices) {
+            Some(value) => value,
+            None => {
+                self.events
+                    .push_back(BotEvent::PerformTradeAction(TradeAction::Decline));
+                return Ok(());
+            }
+        };
+        let own_value = own_items_value + self.coin_value(&trade, own_party);
+
+        let action = if own_value < their_value {
+            TradeAction::AddItem {
+                item: self
+                    .own_coin_slot()
+                    .ok_or("Bot has no coins to trade with")?,
+                quantity: their_value - own_value,
+                ours: true,
+            }
+        } else if own_value > their_value {
+            TradeAction::RemoveItem {
+                item: self
+                    .own_coin_slot()
+                    .ok_or("Bot has no coins to trade with")?,
+                quantity: own_value - their_value,
+                ours: true,
+            }
+        } else if own_items_value == 0 && their_items_value == 0 {
+            // Both sides are still empty (or hold only coins with nothing to price them
+            // against); wait instead of accepting a vacuous zero-value trade.
+            return Ok(());
+        } else {
+            TradeAction::Accept(trade.phase)
+        };
+
+        self.events.push_back(BotEvent::PerformTradeAction(action));
+
+        Ok(())
+    }
+
+    /// Sums the coin value of every non-coin item the given party has placed in the trade
+    /// window, looking each one up by its `ItemDefinitionId` in `prices`. Returns `None` if the
+    /// party has placed something with no configured price. Coins are skipped here and counted
+    /// by [`coin_value`] instead, so a coin-only offer prices to zero.
+    fn price_offer(
+        &self,
+        trade: &PendingTrade,
+        party: usize,
+        prices: &HashMap<String, u32>,
+    ) -> Option<u32> {
+        let owner = trade.parties[party];
+        let offer = trade.offers[party]
+            .iter()
+            .map(|(&slot, &quantity)| Some((self.slot_item_id(owner, slot)?, quantity)))
+            .collect::<Option<Vec<_>>>()?;
+
+        Self::price_resolved_offer(&offer, prices)
+    }
+
+    /// Pure pricing logic behind [`price_offer`], split out so it can be unit tested without a
+    /// live connection: `offer` is every `(ItemDefinitionId, quantity)` pair a party has placed,
+    /// with item ids already resolved from the shared inventory snapshot.
+    fn price_resolved_offer(offer: &[(String, u32)], prices: &HashMap<String, u32>) -> Option<u32> {
+        let mut total = 0;
+
+        for (item_id, quantity) in offer {
+            if item_id == COIN_ITEM_ID {
+                continue;
+            }
+
+            total += *prices.get(item_id)? * quantity;
+        }
+
+        Some(total)
+    }
+
+    /// Returns the amount of coin the given party has already placed in the trade window.
+    fn coin_value(&self, trade: &PendingTrade, party: usize) -> u32 {
+        let owner = trade.parties[party];
+
+        trade.offers[party]
+            .iter()
+            .filter_map(|(&slot, &quantity)| {
+                (self.slot_item_id(owner, slot)? == COIN_ITEM_ID).then_some(quantity)
+            })
+            .sum()
+    }
+
+    /// Looks up the `ItemDefinitionId` string of whatever `owner` (either party to the trade)
+    /// has in `slot`, using the inventory snapshots the server shares for the duration of a
+    /// trade. Returns `None` if the slot is empty or the item has no simple id.
+    fn slot_item_id(&self, owner: Uid, slot: InvSlotId) -> Option<String> {
+        let inventories = self.client.inventories();
+        let inventory = inventories.get(&owner)?.as_ref()?;
+        let item = inventory.get(slot)?;
+
+        match item.item_definition_id() {
+            ItemDefinitionId::Simple(item_id) => Some(item_id),
+            _ => None,
+        }
+    }
+
+    /// Finds the slot in the bot's own inventory holding the item with the given
+    /// `ItemDefinitionId`.
+    fn own_slot_for_item(&self, item_id: &str) -> Option<InvSlotId> {
+        let inventory: &Inventory = self.client.inventory()?;
+
+        inventory.slots_with_id().find_map(|(slot, item)| {
+            let slot_item_id = item?.item_definition_id();
+
+            matches!(slot_item_id, ItemDefinitionId::Simple(id) if id == item_id).then_some(slot)
+        })
+    }
+
+    /// Finds the slot in the bot's own inventory holding `common.items.utility.coins`.
+    fn own_coin_slot(&self) -> Option<InvSlotId> {
+        self.own_slot_for_item(COIN_ITEM_ID)
+    }
+
+    /// Dispatches a parsed, permission- and argument-count-validated command to its handler.
+    fn dispatch_command(
+        &mut self,
+        command: command::ParsedCommand,
+        sender_uid: Uid,
+        sender_name: String,
+    ) -> Result<(), String> {
+        match command.name {
+            CommandName::Inv => self.command_inv(sender_uid, sender_name, command.args),
+            CommandName::Kick => self.command_kick(sender_name, command.args),
+            CommandName::Promote => self.command_promote(sender_name, command.args)?,
+            CommandName::Demote => self.command_demote(sender_name, command.args)?,
+            CommandName::Buy => self.command_buy(sender_uid, sender_name, command.args),
+        }
+
+        Ok(())
+    }
+
+    fn command_inv(&mut self, sender_uid: Uid, sender_name: String, args: Vec<String>) {
+        if args.is_empty() {
+            self.events.push_back(BotEvent::InvitePlayer(sender_uid));
+            return;
+        }
+
+        for arg in args {
+            if let Some(&uid) = self.find_uid(&arg) {
+                self.events.push_back(BotEvent::InvitePlayer(uid));
+                self.events.push_back(BotEvent::SendTell(
+                    sender_name.clone(),
+                    format!("Invited {}", arg),
+                ));
+            } else {
+                self.events.push_back(BotEvent::SendTell(
+                    sender_name.clone(),
+                    format!("Failed to find player {}", arg),
+                ));
+            }
+        }
+    }
+
+    fn command_kick(&mut self, sender_name: String, args: Vec<String>) {
+        for arg in args {
+            if let Some(&uid) = self.find_uid(&arg) {
+                self.events.push_back(BotEvent::KickPlayer(uid));
+            } else {
+                self.events.push_back(BotEvent::SendTell(
+                    sender_name.clone(),
+                    format!("Failed to find player {}", arg),
+                ));
+            }
+        }
+    }
+
+    fn command_promote(&mut self, sender_name: String, args: Vec<String>) -> Result<(), String> {
+        for arg in args {
+            if !self.client.players().any(|player| player == arg) {
+                self.events.push_back(BotEvent::SendTell(
+                    sender_name.clone(),
+                    format!("Failed to find player {}", arg),
+                ));
+
+                continue;
+            }
+
+            self.roles.promote(&arg)?;
+            self.events.push_back(BotEvent::SendTell(
+                sender_name.clone(),
+                format!("Promoted {}", arg),
+            ));
         }
 
         Ok(())
     }
 
+    fn command_demote(&mut self, sender_name: String, args: Vec<String>) -> Result<(), String> {
+        for arg in args {
+            if !self.client.players().any(|player| player == arg) {
+                self.events.push_back(BotEvent::SendTell(
+                    sender_name.clone(),
+                    format!("Failed to find player {}", arg),
+                ));
+
+                continue;
+            }
+
+            match self.roles.demote(&arg) {
+                Ok(()) => {
+                    self.events.push_back(BotEvent::SendTell(
+                        sender_name.clone(),
+                        format!("Demoted {}", arg),
+                    ));
+                }
+                Err(error) => {
+                    self.events
+                        .push_back(BotEvent::SendTell(sender_name.clone(), error));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Places an item the bot is willing to sell into its side of an already-open trade, so the
+    /// player can pay for it. `manage_trade` prices the placed item against `sell_prices` and
+    /// balances the bot's coin offer from there, exactly as it would for a player-initiated sell.
+    fn command_buy(&mut self, sender_uid: Uid, sender_name: String, args: Vec<String>) {
+        let item_id = &args[0];
+        let quantity = match args.get(1) {
+            Some(quantity) => match quantity.parse() {
+                Ok(quantity) => quantity,
+                Err(_) => {
+                    self.events.push_back(BotEvent::SendTell(
+                        sender_name,
+                        "Quantity must be a whole number".to_string(),
+                    ));
+                    return;
+                }
+            },
+            None => 1,
+        };
+
+        if !self.sell_prices.contains_key(item_id) {
+            self.events.push_back(BotEvent::SendTell(
+                sender_name,
+                format!("{item_id} is not for sale"),
+            ));
+            return;
+        }
+
+        let Some((_, trade, _)) = self.client.pending_trade() else {
+            self.events.push_back(BotEvent::SendTell(
+                sender_name,
+                "Start a trade with me before buying".to_string(),
+            ));
+            return;
+        };
+        let is_party_to_trade = trade.which_party(sender_uid).is_some();
+
+        if !is_party_to_trade {
+            self.events.push_back(BotEvent::SendTell(
+                sender_name,
+                "You are not a party to that trade".to_string(),
+            ));
+            return;
+        }
+
+        let Some(slot) = self.own_slot_for_item(item_id) else {
+            self.events.push_back(BotEvent::SendTell(
+                sender_name,
+                format!("Out of stock: {item_id}"),
+            ));
+            return;
+        };
+
+        self.events
+            .push_back(BotEvent::PerformTradeAction(TradeAction::AddItem {
+                item: slot,
+                quantity,
+                ours: true,
+            }));
+    }
+
     /// Finds the name of a player by their Uid.
     fn find_player_alias<'a>(&'a self, uid: &Uid) -> Result<&'a String, String> {
         self.client
@@ -325,13 +670,71 @@ impl Bot {
     }
 }
 
+/// Connects to `game_server` and drives the client through character selection, returning a
+/// freshly ticking [`Client`] and [`Clock`] once the character is in the world. Used both for
+/// the initial connection and for reconnecting after a dropped session.
+fn connect_and_select_character(
+    game_server: String,
+    auth_server: &str,
+    username: &str,
+    password: &str,
+    character: &str,
+) -> Result<(Client, Clock), String> {
+    info!("Connecting to veloren");
+
+    let mut client = connect_to_veloren(game_server, auth_server, username, password)?;
+    let mut clock = Clock::new(CLIENT_TPS);
+
+    client.load_character_list();
+
+    while client.character_list().loading {
+        client
+            .tick(ControllerInputs::default(), clock.dt())
+            .map_err(|error| format!("{error:?}"))?;
+        clock.tick();
+    }
+
+    let character_id = client
+        .character_list()
+        .characters
+        .iter()
+        .find(|character_item| character_item.character.alias == character)
+        .ok_or_else(|| format!("No character named {character}"))?
+        .character
+        .id
+        .ok_or("Failed to get character ID")?;
+
+    info!("Selecting a character");
+
+    // This loop waits and retries requesting the character in the case that the character has
+    // logged out too recently.
+    while client.position().is_none() {
+        client.request_character(
+            character_id,
+            ViewDistances {
+                terrain: 4,
+                entity: 4,
+            },
+        );
+
+        client
+            .tick(ControllerInputs::default(), clock.dt())
+            .map_err(|error| format!("{error:?}"))?;
+        clock.tick();
+    }
+
+    Ok((client, clock))
+}
+
 fn connect_to_veloren(
     game_server: String,
     auth_server: &str,
     username: &str,
     password: &str,
 ) -> Result<Client, String> {
-    let runtime = Arc::new(Runtime::new().unwrap());
+    let runtime = Arc::new(
+        Runtime::new().map_err(|error| format!("Failed to start async runtime: {error}"))?,
+    );
     let runtime2 = Arc::clone(&runtime);
 
     runtime
@@ -353,3 +756,61 @@ fn connect_to_veloren(
         ))
         .map_err(|error| format!("{error:?}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(pairs: &[(&str, u32)]) -> HashMap<String, u32> {
+        pairs
+            .iter()
+            .map(|(item_id, price)| (item_id.to_string(), *price))
+            .collect()
+    }
+
+    fn offer(pairs: &[(&str, u32)]) -> Vec<(String, u32)> {
+        pairs
+            .iter()
+            .map(|(item_id, quantity)| (item_id.to_string(), *quantity))
+            .collect()
+    }
+
+    #[test]
+    fn empty_offer_prices_to_zero() {
+        let prices = prices(&[]);
+
+        assert_eq!(Bot::price_resolved_offer(&offer(&[]), &prices), Some(0));
+    }
+
+    #[test]
+    fn priced_item_sums_by_quantity() {
+        let prices = prices(&[("common.items.food.apple", 2)]);
+        let offer = offer(&[("common.items.food.apple", 3)]);
+
+        assert_eq!(Bot::price_resolved_offer(&offer, &prices), Some(6));
+    }
+
+    #[test]
+    fn unpriced_item_is_unresolvable() {
+        let prices = prices(&[]);
+        let offer = offer(&[("common.items.food.apple", 1)]);
+
+        assert_eq!(Bot::price_resolved_offer(&offer, &prices), None);
+    }
+
+    #[test]
+    fn coin_only_offer_prices_to_zero() {
+        let prices = prices(&[]);
+        let offer = offer(&[(COIN_ITEM_ID, 50)]);
+
+        assert_eq!(Bot::price_resolved_offer(&offer, &prices), Some(0));
+    }
+
+    #[test]
+    fn coins_alongside_a_priced_item_are_excluded_from_the_total() {
+        let prices = prices(&[("common.items.food.apple", 2)]);
+        let offer = offer(&[("common.items.food.apple", 3), (COIN_ITEM_ID, 50)]);
+
+        assert_eq!(Bot::price_resolved_offer(&offer, &prices), Some(6));
+    }
+}
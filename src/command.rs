@@ -0,0 +1,171 @@
+//! Chat command parsing and registry.
+//!
+//! Each command declares its calling convention once, here, instead of the role check and arg
+//! validation being copy-pasted into every `match` arm in [`crate::bot`].
+
+use crate::roles::Role;
+
+/// The chat commands this bot understands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandName {
+    Inv,
+    Kick,
+    Promote,
+    Demote,
+    Buy,
+}
+
+/// Static description of a command's calling convention.
+struct CommandSpec {
+    name: CommandName,
+    keyword: &'static str,
+    min_args: usize,
+    max_args: usize,
+    min_role: Role,
+    /// Noun phrase used in the "you must specify ..." response when `min_args` isn't met.
+    missing_args_hint: &'static str,
+}
+
+const REGISTRY: &[CommandSpec] = &[
+    CommandSpec {
+        name: CommandName::Inv,
+        keyword: "inv",
+        min_args: 0,
+        max_args: usize::MAX,
+        min_role: Role::Moderator,
+        // Unreachable: `min_args` is 0, so the sender is never told to specify a player.
+        // Documented rather than dropped so `CommandSpec` can stay a plain struct literal.
+        missing_args_hint: "a player to invite",
+    },
+    CommandSpec {
+        name: CommandName::Kick,
+        keyword: "kick",
+        min_args: 1,
+        max_args: usize::MAX,
+        min_role: Role::Moderator,
+        missing_args_hint: "a player to kick",
+    },
+    CommandSpec {
+        name: CommandName::Promote,
+        keyword: "admin",
+        min_args: 1,
+        max_args: usize::MAX,
+        min_role: Role::Owner,
+        missing_args_hint: "a player to promote",
+    },
+    CommandSpec {
+        name: CommandName::Demote,
+        keyword: "demote",
+        min_args: 1,
+        max_args: usize::MAX,
+        min_role: Role::Owner,
+        missing_args_hint: "a player to demote",
+    },
+    CommandSpec {
+        name: CommandName::Buy,
+        keyword: "buy",
+        min_args: 1,
+        max_args: 2,
+        min_role: Role::Member,
+        missing_args_hint: "an item to buy",
+    },
+];
+
+/// A chat command that passed parsing, permission and argument-count validation.
+pub struct ParsedCommand {
+    pub name: CommandName,
+    pub args: Vec<String>,
+}
+
+/// Why a chat message could not be turned into a [`ParsedCommand`].
+pub enum CommandError {
+    /// The first word isn't a registered command keyword; callers should silently ignore it.
+    Unknown,
+    /// The sender doesn't hold the command's required role.
+    InsufficientRole { required: Role },
+    /// Too few or too many arguments were supplied.
+    ArgCount { message: String },
+}
+
+/// Tokenizes `content` and validates it against the registry, given the sender's [`Role`].
+pub fn parse(content: &str, sender_role: Role) -> Result<ParsedCommand, CommandError> {
+    let mut words = content.split_whitespace();
+    let keyword = words.next().ok_or(CommandError::Unknown)?;
+
+    let spec = REGISTRY
+        .iter()
+        .find(|spec| spec.keyword == keyword)
+        .ok_or(CommandError::Unknown)?;
+
+    if sender_role < spec.min_role {
+        return Err(CommandError::InsufficientRole {
+            required: spec.min_role,
+        });
+    }
+
+    let args: Vec<String> = words.map(str::to_string).collect();
+
+    if args.len() < spec.min_args {
+        return Err(CommandError::ArgCount {
+            message: format!("You must specify {}", spec.missing_args_hint),
+        });
+    }
+
+    if args.len() > spec.max_args {
+        return Err(CommandError::ArgCount {
+            message: format!("{} takes at most {} arguments", spec.keyword, spec.max_args),
+        });
+    }
+
+    Ok(ParsedCommand {
+        name: spec.name,
+        args,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_keyword_is_ignored() {
+        assert!(matches!(
+            parse("dance", Role::Owner),
+            Err(CommandError::Unknown)
+        ));
+    }
+
+    #[test]
+    fn insufficient_role_is_rejected() {
+        assert!(matches!(
+            parse("kick griefer", Role::Member),
+            Err(CommandError::InsufficientRole {
+                required: Role::Moderator
+            })
+        ));
+    }
+
+    #[test]
+    fn missing_required_arg_is_rejected() {
+        assert!(matches!(
+            parse("kick", Role::Moderator),
+            Err(CommandError::ArgCount { .. })
+        ));
+    }
+
+    #[test]
+    fn too_many_args_is_rejected() {
+        assert!(matches!(
+            parse("buy coins 1 2", Role::Member),
+            Err(CommandError::ArgCount { .. })
+        ));
+    }
+
+    #[test]
+    fn valid_command_parses_name_and_args() {
+        let parsed = parse("buy coins 5", Role::Member).ok().unwrap();
+
+        assert_eq!(parsed.name, CommandName::Buy);
+        assert_eq!(parsed.args, vec!["coins".to_string(), "5".to_string()]);
+    }
+}
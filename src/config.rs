@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use serde::Deserialize;
 
 #[derive(Deserialize)]
@@ -6,5 +8,26 @@ pub struct Secrets {
     pub username: String,
     pub password: String,
     pub character: String,
-    pub admin_list: Vec<String>,
+    /// Players who hold the fixed, non-demotable `Owner` role.
+    pub owners: Vec<String>,
+    /// File that runtime moderator promotions are persisted to and reloaded from.
+    pub roles_path: String,
+
+    /// World position the bot should stand at, e.g. a market stall.
+    pub position: [f32; 3],
+    /// Yaw, in radians, the bot should face while standing at `position`.
+    pub orientation: f32,
+
+    /// Messages the bot cycles through and broadcasts to the region chat.
+    #[serde(default)]
+    pub announcements: Vec<String>,
+    /// How often, in seconds, to broadcast the next announcement.
+    pub announcement_interval_secs: u64,
+
+    /// Coin price, per unit, the bot will pay for an item with this `ItemDefinitionId` string.
+    #[serde(default)]
+    pub buy_prices: HashMap<String, u32>,
+    /// Coin price, per unit, the bot will charge for an item with this `ItemDefinitionId` string.
+    #[serde(default)]
+    pub sell_prices: HashMap<String, u32>,
 }
@@ -1,9 +1,11 @@
 #![feature(duration_constructors)]
 
 mod bot;
+mod command;
 mod config;
+mod roles;
 
-use std::{env::var, fs::read_to_string};
+use std::{env::var, fs::read_to_string, path::PathBuf, time::Duration};
 
 use bot::Bot;
 use config::Secrets;
@@ -28,7 +30,14 @@ fn main() {
         secrets.username,
         &secrets.password,
         &secrets.character,
-        secrets.admin_list,
+        secrets.owners,
+        PathBuf::from(secrets.roles_path),
+        secrets.buy_prices,
+        secrets.sell_prices,
+        secrets.position,
+        secrets.orientation,
+        secrets.announcements,
+        Duration::from_secs(secrets.announcement_interval_secs),
     )
     .expect("Failed to create bot");
 
@@ -0,0 +1,149 @@
+//! Tiered permission roles, persisted to disk so moderator promotions survive reconnects.
+
+use std::{fs, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// A privilege tier a player can hold. Variants are ordered from least to most privileged so
+/// they can be compared directly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+pub enum Role {
+    Member,
+    Moderator,
+    Owner,
+}
+
+/// Tracks who holds which [`Role`]. Owners are fixed at construction from `Secrets` and cannot
+/// be demoted; moderators can be promoted and demoted at runtime and are persisted to disk
+/// whenever they change.
+pub struct RoleStore {
+    owners: Vec<String>,
+    moderators: Vec<String>,
+    path: PathBuf,
+}
+
+impl RoleStore {
+    /// Creates a store with a fixed `owners` set, loading any previously-persisted moderators
+    /// from `path` if it exists.
+    pub fn load(owners: Vec<String>, path: PathBuf) -> Result<Self, String> {
+        let moderators = match fs::read_to_string(&path) {
+            Ok(content) => {
+                toml::from_str::<PersistedRoles>(&content)
+                    .map_err(|error| format!("Failed to parse {}: {error}", path.display()))?
+                    .moderators
+            }
+            Err(_) => Vec::new(),
+        };
+
+        Ok(Self {
+            owners,
+            moderators,
+            path,
+        })
+    }
+
+    /// Returns the role held by `name`, defaulting to [`Role::Member`].
+    pub fn role_of(&self, name: &str) -> Role {
+        if self.owners.iter().any(|owner| owner == name) {
+            Role::Owner
+        } else if self.moderators.iter().any(|moderator| moderator == name) {
+            Role::Moderator
+        } else {
+            Role::Member
+        }
+    }
+
+    /// Promotes `name` to moderator and persists the change. No-ops if `name` already holds
+    /// moderator or owner.
+    pub fn promote(&mut self, name: &str) -> Result<(), String> {
+        if self.role_of(name) >= Role::Moderator {
+            return Ok(());
+        }
+
+        self.moderators.push(name.to_string());
+        self.persist()
+    }
+
+    /// Demotes a moderator back to member and persists the change. Returns an error without
+    /// changing or persisting anything if `name` is an owner or doesn't hold moderator.
+    pub fn demote(&mut self, name: &str) -> Result<(), String> {
+        match self.role_of(name) {
+            Role::Owner => Err(format!("{name} is an owner and cannot be demoted")),
+            Role::Member => Err(format!("{name} is not a moderator")),
+            Role::Moderator => {
+                self.moderators.retain(|moderator| moderator != name);
+                self.persist()
+            }
+        }
+    }
+
+    fn persist(&self) -> Result<(), String> {
+        let persisted = PersistedRoles {
+            moderators: self.moderators.clone(),
+        };
+        let content = toml::to_string_pretty(&persisted).map_err(|error| format!("{error:?}"))?;
+
+        fs::write(&self.path, content).map_err(|error| format!("{error:?}"))
+    }
+}
+
+#[derive(Deserialize, Serialize, Default)]
+struct PersistedRoles {
+    #[serde(default)]
+    moderators: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `name` distinguishes the backing file per test so parallel runs don't race on the same
+    /// path; nothing reads this file back within a single test, so persistence itself isn't
+    /// asserted on here.
+    fn store(name: &str) -> RoleStore {
+        RoleStore {
+            owners: vec!["owner".to_string()],
+            moderators: Vec::new(),
+            path: std::env::temp_dir().join(format!("group_bot-roles-test-{name}.toml")),
+        }
+    }
+
+    #[test]
+    fn role_ordering_ranks_owner_above_moderator_above_member() {
+        assert!(Role::Owner > Role::Moderator);
+        assert!(Role::Moderator > Role::Member);
+    }
+
+    #[test]
+    fn role_of_defaults_to_member() {
+        let store = store("role_of_defaults_to_member");
+
+        assert_eq!(store.role_of("nobody"), Role::Member);
+        assert_eq!(store.role_of("owner"), Role::Owner);
+    }
+
+    #[test]
+    fn promote_then_demote_round_trips_through_moderator() {
+        let mut store = store("promote_then_demote_round_trips_through_moderator");
+
+        store.promote("alice").unwrap();
+        assert_eq!(store.role_of("alice"), Role::Moderator);
+
+        store.demote("alice").unwrap();
+        assert_eq!(store.role_of("alice"), Role::Member);
+    }
+
+    #[test]
+    fn demote_rejects_non_moderator() {
+        let mut store = store("demote_rejects_non_moderator");
+
+        assert!(store.demote("alice").is_err());
+    }
+
+    #[test]
+    fn demote_rejects_owner() {
+        let mut store = store("demote_rejects_owner");
+
+        assert!(store.demote("owner").is_err());
+    }
+}